@@ -0,0 +1,123 @@
+use chrono::Local;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const DEFAULT_MAX_SIZE_BYTES: u64 = 1024 * 1024;
+pub const DEFAULT_MAX_ROTATED: usize = 7;
+
+pub struct LogEntry {
+    pub date_or_range: String,
+    pub custom_seed: bool,
+    pub format: String,
+    pub exit_status: i32,
+    pub duration: Duration,
+}
+
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+// Renames the active log out of the way, shifting any previously rotated
+// files up by one and dropping the oldest once `max_rotated` is exceeded.
+fn rotate(path: &Path, max_rotated: usize) {
+    let _ = fs::remove_file(rotated_path(path, max_rotated));
+    for n in (1..max_rotated).rev() {
+        let _ = fs::rename(rotated_path(path, n), rotated_path(path, n + 1));
+    }
+    let _ = fs::rename(path, rotated_path(path, 1));
+}
+
+pub fn append(path: &Path, entry: &LogEntry, max_size: u64, max_rotated: usize) {
+    if let Ok(meta) = fs::metadata(path) {
+        if meta.len() >= max_size {
+            rotate(path, max_rotated);
+        }
+    }
+
+    let line = format!(
+        "{} date_or_range={} custom_seed={} format={} exit_status={} duration_ms={}\n",
+        Local::now().to_rfc3339(),
+        entry.date_or_range,
+        entry.custom_seed,
+        entry.format,
+        entry.exit_status,
+        entry.duration.as_millis(),
+    );
+
+    if let Ok(mut file) = fs::OpenOptions::new().create(true).append(true).open(path) {
+        let _ = file.write_all(line.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_log_path(label: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "rspotd-audit-test-{}-{}.log",
+            std::process::id(),
+            label
+        ))
+    }
+
+    fn entry() -> LogEntry {
+        LogEntry {
+            date_or_range: "2026-07-28".to_string(),
+            custom_seed: false,
+            format: "text".to_string(),
+            exit_status: 0,
+            duration: Duration::from_millis(1),
+        }
+    }
+
+    #[test]
+    fn append_does_not_rotate_below_max_size() {
+        let path = unique_log_path("below");
+        let _ = fs::remove_file(&path);
+        fs::write(&path, "x".repeat(9)).unwrap();
+
+        append(&path, &entry(), 10, 7);
+
+        assert!(!rotated_path(&path, 1).exists());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn append_rotates_once_size_reaches_max_size() {
+        let path = unique_log_path("at-limit");
+        let _ = fs::remove_file(&path);
+        let rotated = rotated_path(&path, 1);
+        let _ = fs::remove_file(&rotated);
+        fs::write(&path, "x".repeat(10)).unwrap();
+
+        append(&path, &entry(), 10, 7);
+
+        assert!(rotated.exists());
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&rotated);
+    }
+
+    #[test]
+    fn rotate_drops_the_oldest_file_beyond_max_rotated() {
+        let path = unique_log_path("bounded");
+        let oldest = rotated_path(&path, 2);
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&rotated_path(&path, 1));
+        let _ = fs::remove_file(&oldest);
+        fs::write(&path, "active").unwrap();
+        fs::write(&oldest, "oldest").unwrap();
+
+        rotate(&path, 2);
+
+        assert!(!oldest.exists());
+        assert!(!path.exists());
+        assert!(rotated_path(&path, 1).exists());
+
+        let _ = fs::remove_file(rotated_path(&path, 1));
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Settings {
+    pub seed: Option<String>,
+    pub format: Option<String>,
+    pub default_output: Option<String>,
+    pub log: Option<String>,
+    pub log_max_size: Option<u64>,
+    pub log_max_rotated: Option<usize>,
+}
+
+/// `~/.config/rspotd/config.toml` on Linux, and the platform equivalent elsewhere.
+pub fn default_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("rspotd")
+        .join("config.toml")
+}
+
+/// Missing or unparseable config files are treated as an empty `Settings`,
+/// so a fresh install works with no config file present.
+pub fn load(path: &Path) -> Settings {
+    match fs::read_to_string(path) {
+        Ok(contents) => toml::from_str(&contents).unwrap_or_default(),
+        Err(_) => Settings::default(),
+    }
+}
+
+pub fn save(path: &Path, settings: &Settings) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let contents = toml::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(path, contents).map_err(|e| e.to_string())
+}
@@ -1,15 +1,22 @@
 #![allow(warnings)]
 
-use chrono::Local;
+use arboard::Clipboard;
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
 use clap::builder::PossibleValuesParser;
 use clap::Parser;
 use rspotd::{generate, generate_multiple, seed_to_des};
 use serde_json::to_string_pretty;
+use std::collections::BTreeMap;
 use std::fs::{File, OpenOptions};
 use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Instant;
 use std::{path::Path, process::exit};
 use std::writeln;
 
+mod audit;
+mod config;
+
 #[derive(Parser)]
 #[clap(
     author = "Shea Zerda",
@@ -46,8 +53,8 @@ struct Args {
     #[arg(
         short = 'f',
         long = "format",
-        value_parser = PossibleValuesParser::new(["json", "text"]),
-        help="Password output format, either text or json"
+        value_parser = PossibleValuesParser::new(["json", "text", "csv"]),
+        help="Password output format: text, json, or csv"
     )]
     format: Option<String>,
 
@@ -73,42 +80,292 @@ struct Args {
         help = "Print output to console even when writing to file"
     )]
     verbose: bool,
+
+    #[arg(
+        short = 'c',
+        long = "clipboard",
+        conflicts_with = "range",
+        help = "Copy the generated password to the system clipboard instead of printing it"
+    )]
+    clipboard: bool,
+
+    #[arg(
+        long = "config",
+        help = "Path to a config file, overriding the platform default config location"
+    )]
+    config: Option<String>,
+
+    #[arg(
+        long = "save-config",
+        help = "Write the currently-resolved seed/format/output back out to the config file"
+    )]
+    save_config: bool,
+
+    #[arg(
+        long = "log",
+        help = "Append a rolling audit log entry for this invocation to the given file"
+    )]
+    log: Option<String>,
+
+    #[arg(
+        long = "log-max-size",
+        help = "Rotate the audit log once it reaches this many bytes (default 1 MiB)"
+    )]
+    log_max_size: Option<u64>,
+
+    #[arg(
+        long = "log-max-rotated",
+        help = "Number of rotated audit log files to keep (default 7)"
+    )]
+    log_max_rotated: Option<usize>,
 }
 
 fn current_date() -> String {
     Local::now().format("%Y-%m-%d").to_string()
 }
 
+// Renders a date -> password map according to `format`. `with_date_prefix` controls
+// whether the text format prefixes each line with its date, which is wanted for
+// ranges but not for a single generated password.
+fn render(entries: &BTreeMap<String, String>, format: &str, with_date_prefix: bool) -> String {
+    match format {
+        "csv" => {
+            let mut out = String::from("date,password\n");
+            for (date, password) in entries {
+                out.push_str(&format!("{},{}\n", date, password));
+            }
+            out
+        }
+        "json" => to_string_pretty(entries).unwrap_or_default(),
+        _ => {
+            let mut out = String::new();
+            for (date, password) in entries {
+                if with_date_prefix {
+                    out.push_str(&format!("{}: {}\n", date, password));
+                } else {
+                    out.push_str(&format!("{}\n", password));
+                }
+            }
+            out
+        }
+    }
+}
+
+fn weekday_from_name(name: &str) -> Option<Weekday> {
+    match name {
+        "monday" => Some(Weekday::Mon),
+        "tuesday" => Some(Weekday::Tue),
+        "wednesday" => Some(Weekday::Wed),
+        "thursday" => Some(Weekday::Thu),
+        "friday" => Some(Weekday::Fri),
+        "saturday" => Some(Weekday::Sat),
+        "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+// Resolves a relative or natural-language date expression ("today", "tomorrow",
+// "next friday", "in 3 days", ...) against Local::now(), returning the canonical
+// %Y-%m-%d string the library expects. Inputs already in %Y-%m-%d form pass through.
+fn resolve_date(input: &str) -> Result<String, String> {
+    if NaiveDate::parse_from_str(input, "%Y-%m-%d").is_ok() {
+        return Ok(input.to_string());
+    }
+
+    let today = Local::now().date_naive();
+    let lower = input.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+
+    let resolved = match words.as_slice() {
+        ["today"] => Some(today),
+        ["tomorrow"] => Some(today + Duration::days(1)),
+        ["yesterday"] => Some(today - Duration::days(1)),
+        ["in", n, "day" | "days"] => n.parse::<i64>().ok().map(|n| today + Duration::days(n)),
+        ["in", n, "week" | "weeks"] => n.parse::<i64>().ok().map(|n| today + Duration::weeks(n)),
+        ["last", "week"] => Some(today - Duration::weeks(1)),
+        ["next", "week"] => Some(today + Duration::weeks(1)),
+        ["next", day] => weekday_from_name(day).map(|target| {
+            let mut candidate = today + Duration::days(1);
+            while candidate.weekday() != target {
+                candidate += Duration::days(1);
+            }
+            candidate
+        }),
+        ["last", day] => weekday_from_name(day).map(|target| {
+            let mut candidate = today - Duration::days(1);
+            while candidate.weekday() != target {
+                candidate -= Duration::days(1);
+            }
+            candidate
+        }),
+        [day] => weekday_from_name(day).map(|target| {
+            let mut candidate = today;
+            while candidate.weekday() != target {
+                candidate += Duration::days(1);
+            }
+            candidate
+        }),
+        _ => None,
+    };
+
+    match resolved {
+        Some(date) => Ok(date.format("%Y-%m-%d").to_string()),
+        None => Err(format!(
+            "Unable to parse '{}' as a date; expected YYYY-MM-DD or a relative expression like \"tomorrow\" or \"next friday\"",
+            input
+        )),
+    }
+}
+
+// Appends an audit log entry (if logging is enabled) recording this invocation,
+// then exits with `status`. Never logs the seed value itself.
+fn log_and_exit(
+    log_path: &Option<PathBuf>,
+    log_max_size: u64,
+    log_max_rotated: usize,
+    date_or_range: &str,
+    custom_seed: bool,
+    format: &str,
+    start: Instant,
+    status: i32,
+) -> ! {
+    if let Some(path) = log_path {
+        audit::append(
+            path,
+            &audit::LogEntry {
+                date_or_range: date_or_range.to_string(),
+                custom_seed,
+                format: format.to_string(),
+                exit_status: status,
+                duration: start.elapsed(),
+            },
+            log_max_size,
+            log_max_rotated,
+        );
+    }
+    exit(status);
+}
+
 fn main() {
     use rspotd::vals::DEFAULT_SEED;
+    let start = Instant::now();
     let args = Args::parse();
+
+    // load the config file, merging CLI args on top with precedence
+    // CLI flag > config file > built-in default
+    let config_path = args
+        .config
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(config::default_path);
+    let file_settings = config::load(&config_path);
+
     let format;
     let seed;
     let output;
     let path;
 
     // determine output format
-    if args.format.is_none() {
-        format = "text";
+    if let Some(f) = args.format.as_ref() {
+        format = f.clone();
+    } else if let Some(f) = file_settings.format.as_ref() {
+        format = f.clone();
     } else {
-        format = args.format.as_ref().unwrap();
+        format = "text".to_string();
     }
 
     // determine output file, if any
-    if args.output.is_none() {
+    let output_arg = args.output.clone().or(file_settings.default_output.clone());
+    if output_arg.is_none() {
         path = Path::new(".").to_path_buf();
         output = false;
     } else {
-        let user_input = args.output.unwrap();
+        let user_input = output_arg.unwrap();
         path = Path::new(".").join(user_input.to_string());
         output = true;
     }
 
     // determine seed
-    if args.seed.is_none() {
-        seed = DEFAULT_SEED;
-    } else {
-        seed = args.seed.as_ref().unwrap().as_str();
+    let seed_owned = args
+        .seed
+        .clone()
+        .or(file_settings.seed.clone())
+        .unwrap_or_else(|| DEFAULT_SEED.to_string());
+    seed = seed_owned.as_str();
+    let custom_seed = args.seed.is_some() || file_settings.seed.is_some();
+
+    // determine audit log file and rotation limits, if any
+    let log_path = args
+        .log
+        .clone()
+        .or(file_settings.log.clone())
+        .map(PathBuf::from);
+    let log_max_size = args
+        .log_max_size
+        .or(file_settings.log_max_size)
+        .unwrap_or(audit::DEFAULT_MAX_SIZE_BYTES);
+    let log_max_rotated = args
+        .log_max_rotated
+        .or(file_settings.log_max_rotated)
+        .unwrap_or(audit::DEFAULT_MAX_ROTATED);
+
+    if args.save_config {
+        let resolved = config::Settings {
+            seed: Some(seed_owned.clone()),
+            format: Some(format.clone()),
+            default_output: if output {
+                Some(path.display().to_string())
+            } else {
+                None
+            },
+            log: log_path.as_ref().map(|p| p.display().to_string()),
+            log_max_size: Some(log_max_size),
+            log_max_rotated: Some(log_max_rotated),
+        };
+        match config::save(&config_path, &resolved) {
+            Ok(()) => println!("Configuration saved to {}", config_path.display()),
+            Err(e) => {
+                println!("Unable to save configuration: {}", e);
+                log_and_exit(&log_path, log_max_size, log_max_rotated, "-", custom_seed, &format, start, 1);
+            }
+        }
+    }
+
+    // output the DES representation of the seed, for verifying a seed without
+    // generating a password
+    if args.des {
+        let des = seed_to_des(seed);
+        let rendered = match format.as_str() {
+            "json" => to_string_pretty(&serde_json::json!({"seed": seed, "des": des}))
+                .unwrap_or_default(),
+            _ => des,
+        };
+
+        if output {
+            match OpenOptions::new()
+                .create(true)
+                .truncate(true)
+                .write(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    if let Err(e) = file.write_all(format!("{}\n", rendered).as_bytes()) {
+                        println!("Unable to write to file '{}': {}", path.display(), e);
+                        log_and_exit(&log_path, log_max_size, log_max_rotated, "-", custom_seed, &format, start, 1);
+                    }
+                }
+                Err(e) => {
+                    println!("Unable to open file '{}': {}", path.display(), e);
+                    log_and_exit(&log_path, log_max_size, log_max_rotated, "-", custom_seed, &format, start, 1);
+                }
+            }
+            if args.verbose {
+                println!("{}", rendered);
+            }
+        } else {
+            println!("{}", rendered);
+        }
+        log_and_exit(&log_path, log_max_size, log_max_rotated, "-", custom_seed, &format, start, 0);
     }
 
     // determine date or range
@@ -117,13 +374,24 @@ fn main() {
         if args.date.is_none() {
             date = current_date();
         } else {
-            date = args.date.as_ref().unwrap().to_string();
+            date = match resolve_date(args.date.as_ref().unwrap()) {
+                Ok(date) => date,
+                Err(e) => {
+                    println!("{}", e);
+                    log_and_exit(&log_path, log_max_size, log_max_rotated, args.date.as_ref().unwrap(), custom_seed, &format, start, 1);
+                }
+            };
         }
         let result = generate(date.as_ref(), seed);
         if result.is_err() {
             println!("{}", result.unwrap_err());
-            exit(1);
+            log_and_exit(&log_path, log_max_size, log_max_rotated, &date, custom_seed, &format, start, 1);
         } else {
+            let password = result.unwrap();
+            let rendered = render(&BTreeMap::from([(date.clone(), password.clone())]), &format, false);
+            // writing to a file replaces printing to stdout, unless --verbose asks for both
+            let should_print = !output || args.verbose;
+
             if output {
                 let mut file = OpenOptions::new()
                     .create_new(true)
@@ -132,25 +400,64 @@ fn main() {
                     .open(path)
                     .unwrap();
 
-                let potd = format!("{}\n", result.as_ref().unwrap());
-                file.write_all(potd.as_bytes());
-                if args.verbose {
-                    println!("{}\n", result.unwrap());
-                    exit(0)
+                file.write_all(rendered.as_bytes());
+            }
+            if args.clipboard {
+                match Clipboard::new() {
+                    Ok(mut clipboard) => match clipboard.set_text(password.clone()) {
+                        Ok(()) => {
+                            println!("Password copied to clipboard.");
+                            // the whole point of --clipboard is to keep the password out of
+                            // stdout/scrollback by default; only --verbose overrides that
+                            if args.verbose {
+                                print!("{}", rendered);
+                            }
+                            log_and_exit(&log_path, log_max_size, log_max_rotated, &date, custom_seed, &format, start, 0);
+                        }
+                        Err(e) => {
+                            println!("Unable to copy password to clipboard: {}", e);
+                            log_and_exit(&log_path, log_max_size, log_max_rotated, &date, custom_seed, &format, start, 1);
+                        }
+                    },
+                    Err(e) => {
+                        println!("Unable to access clipboard: {}", e);
+                        log_and_exit(&log_path, log_max_size, log_max_rotated, &date, custom_seed, &format, start, 1);
+                    }
                 }
             }
-            println!("{}", result.as_ref().unwrap());
-            exit(0);
+            if should_print {
+                print!("{}", rendered);
+            }
+            log_and_exit(&log_path, log_max_size, log_max_rotated, &date, custom_seed, &format, start, 0);
         }
     } else {
         let range = args.range.unwrap();
-        let begin = &range[0];
-        let end = &range[1];
+        let raw_range_label = format!("{}..{}", range[0], range[1]);
+        let begin = match resolve_date(&range[0]) {
+            Ok(date) => date,
+            Err(e) => {
+                println!("{}", e);
+                log_and_exit(&log_path, log_max_size, log_max_rotated, &raw_range_label, custom_seed, &format, start, 1);
+            }
+        };
+        let end = match resolve_date(&range[1]) {
+            Ok(date) => date,
+            Err(e) => {
+                println!("{}", e);
+                log_and_exit(&log_path, log_max_size, log_max_rotated, &raw_range_label, custom_seed, &format, start, 1);
+            }
+        };
+        let range_label = format!("{}..{}", begin, end);
         let result = generate_multiple(&begin, &end, seed);
         if result.is_err() {
             println!("{}", result.unwrap_err());
-            exit(1);
+            log_and_exit(&log_path, log_max_size, log_max_rotated, &range_label, custom_seed, &format, start, 1);
         } else {
+            let passwords: BTreeMap<String, String> = result.unwrap().into_iter().collect();
+            let rendered = render(&passwords, &format, true);
+            // writing to a file replaces printing to stdout, unless --verbose asks for both
+            let should_print = !output || args.verbose;
+
             if output {
                 let mut file = OpenOptions::new()
                     .write(true)
@@ -166,30 +473,93 @@ fn main() {
                     // file cannot be created due to permissions
                     if file.is_err() {
                         println!("Unable to create file '{}' due to permissions.", path.display());
-                        exit(1);
+                        log_and_exit(&log_path, log_max_size, log_max_rotated, &range_label, custom_seed, &format, start, 1);
                     }
                 }
                 let mut writer = BufWriter::new(file.as_mut().unwrap());
-                let potd = serde_json::to_string_pretty(result.as_ref().unwrap());
-                if potd.is_err() {
-                    println!("{}", potd.as_ref().unwrap_err());
-                    exit(1)
-                } else {
-                    writer.write_all(potd.as_ref().unwrap().as_bytes());
-                    writer.write_all("\n".as_bytes());
-                    if args.verbose {
-                        println!("{}", potd.unwrap());
-                        exit(0);
-                    }
-                }
+                writer.write_all(rendered.as_bytes());
+            }
+            if should_print {
+                print!("{}", rendered);
             }
+            log_and_exit(&log_path, log_max_size, log_max_rotated, &range_label, custom_seed, &format, start, 0);
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_date_passes_through_canonical_form() {
+        assert_eq!(resolve_date("2026-07-28").unwrap(), "2026-07-28");
+    }
+
+    #[test]
+    fn resolve_date_today_matches_current_date() {
+        assert_eq!(resolve_date("today").unwrap(), current_date());
+    }
+
+    #[test]
+    fn resolve_date_tomorrow_is_one_day_after_today() {
+        let today = Local::now().date_naive();
+        let expected = (today + Duration::days(1)).format("%Y-%m-%d").to_string();
+        assert_eq!(resolve_date("tomorrow").unwrap(), expected);
+    }
+
+    #[test]
+    fn resolve_date_yesterday_is_one_day_before_today() {
+        let today = Local::now().date_naive();
+        let expected = (today - Duration::days(1)).format("%Y-%m-%d").to_string();
+        assert_eq!(resolve_date("yesterday").unwrap(), expected);
+    }
 
-    // TODO:
-    // - implement format
-    // - output to file
-    // - verbose (print even when output to file)
-    // - add date formatting
-    //   - default format
-}
\ No newline at end of file
+    #[test]
+    fn resolve_date_in_n_days_offsets_forward() {
+        let today = Local::now().date_naive();
+        let expected = (today + Duration::days(3)).format("%Y-%m-%d").to_string();
+        assert_eq!(resolve_date("in 3 days").unwrap(), expected);
+    }
+
+    #[test]
+    fn resolve_date_next_weekday_is_strictly_after_today_and_matches_weekday() {
+        let today = Local::now().date_naive();
+        let resolved = resolve_date("next friday").unwrap();
+        let resolved_date = NaiveDate::parse_from_str(&resolved, "%Y-%m-%d").unwrap();
+        assert!(resolved_date > today);
+        assert_eq!(resolved_date.weekday(), Weekday::Fri);
+        assert!(resolved_date - today <= Duration::days(7));
+    }
+
+    #[test]
+    fn resolve_date_last_weekday_is_strictly_before_today_and_matches_weekday() {
+        let today = Local::now().date_naive();
+        let resolved = resolve_date("last friday").unwrap();
+        let resolved_date = NaiveDate::parse_from_str(&resolved, "%Y-%m-%d").unwrap();
+        assert!(resolved_date < today);
+        assert_eq!(resolved_date.weekday(), Weekday::Fri);
+        assert!(today - resolved_date <= Duration::days(7));
+    }
+
+    #[test]
+    fn resolve_date_next_and_last_weekday_never_coincide() {
+        let next = resolve_date("next friday").unwrap();
+        let last = resolve_date("last friday").unwrap();
+        assert_ne!(next, last);
+    }
+
+    #[test]
+    fn resolve_date_bare_weekday_name_is_today_or_later() {
+        let today = Local::now().date_naive();
+        let resolved = resolve_date("friday").unwrap();
+        let resolved_date = NaiveDate::parse_from_str(&resolved, "%Y-%m-%d").unwrap();
+        assert!(resolved_date >= today);
+        assert_eq!(resolved_date.weekday(), Weekday::Fri);
+    }
+
+    #[test]
+    fn resolve_date_rejects_unparseable_input() {
+        assert!(resolve_date("whenever").is_err());
+    }
+}